@@ -1,5 +1,7 @@
 use git2::{Repository, Sort, Oid};
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use tauri::Emitter;
 
 #[derive(Serialize, Clone, Debug)]
 pub struct CommitInfo {
@@ -10,6 +12,41 @@ pub struct CommitInfo {
     pub date: i64,
     pub message: String,
     pub refs: Vec<RefInfo>,
+    /// Whether this commit carries a merge of more than one parent.
+    pub is_merge: bool,
+    /// A merge whose resulting tree is identical to one of its parents, i.e.
+    /// the merge introduced no changes of its own. The verification workflow
+    /// usually wants to skip these.
+    pub trivial_merge: bool,
+    /// Signature state: `Unsigned` or `Unverified` (signed, not checked). No
+    /// keyring is consulted here; trust is checked only by [`verify_commit`].
+    pub signature: CommitSignature,
+    /// Column this commit occupies in the rendered DAG, assigned by the
+    /// lane pass in [`get_commits`].
+    pub lane: usize,
+    /// Edge segments that cross this commit's row: continuing lanes
+    /// (`from == to`) and merge edges collapsing into this commit's lane.
+    pub edges: Vec<GraphEdge>,
+}
+
+/// A single `(from_lane, to_lane)` edge segment crossing a graph row.
+#[derive(Serialize, Clone, Debug)]
+pub struct GraphEdge {
+    pub from: usize,
+    pub to: usize,
+}
+
+/// Tri-state (plus an "untrusted" and "unverified" middle) describing a
+/// commit's GPG/SSH signature. `Unverified` is what `get_commits` reports for
+/// every signed commit — the signature is present but was not checked.
+#[derive(Serialize, Clone, Debug)]
+#[serde(tag = "state")]
+pub enum CommitSignature {
+    Unsigned,
+    Unverified,
+    SignedValid { signer: String },
+    SignedUntrusted { signer: String },
+    SignedBad,
 }
 
 #[derive(Serialize, Clone, Debug)]
@@ -22,6 +59,39 @@ pub struct RefInfo {
 pub struct FileChange {
     pub path: String,
     pub status: String,
+    /// Original path for renamed/copied files; `None` otherwise.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub old_path: Option<String>,
+    /// Structured hunks, populated only when the caller asks for the patch.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hunks: Option<Vec<DiffHunk>>,
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct DiffHunk {
+    /// The `@@ -old,+new @@` header line as emitted by git.
+    pub header: String,
+    pub old_start: u32,
+    pub old_lines: u32,
+    pub new_start: u32,
+    pub new_lines: u32,
+    pub lines: Vec<DiffLine>,
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct DiffLine {
+    pub origin: String, // "Context", "Addition", "Deletion"
+    pub old_lineno: Option<u32>,
+    pub new_lineno: Option<u32>,
+    pub content: String,
+}
+
+/// Tunables for diff generation, surfaced so the UI can request more context or
+/// ignore whitespace-only changes.
+#[derive(Deserialize, Default)]
+pub struct DiffConfig {
+    pub context_lines: Option<u32>,
+    pub ignore_whitespace: Option<bool>,
 }
 
 #[tauri::command]
@@ -29,49 +99,7 @@ pub fn get_commits(path: String) -> Result<Vec<CommitInfo>, String> {
     let repo = Repository::open(&path).map_err(|e| e.to_string())?;
 
     // Collect refs to map them to commits
-    let mut ref_map: std::collections::HashMap<String, Vec<RefInfo>> = std::collections::HashMap::new();
-    let mut relevant_oids: std::collections::HashSet<String> = std::collections::HashSet::new();
-
-    let references = repo.references().map_err(|e| e.to_string())?;
-    for reference in references {
-        if let Ok(r) = reference {
-            if let Some(name) = r.name() {
-                if let Some(target) = r.target() {
-                    let kind = if r.is_remote() {
-                        "remote"
-                    } else if r.is_tag() {
-                        "tag"
-                    } else if r.is_branch() {
-                        "branch"
-                    } else {
-                        "other"
-                    };
-
-                    let short_name = r.shorthand().unwrap_or(name).to_string();
-                    let target_oid = target.to_string();
-
-                    ref_map.entry(target_oid.clone()).or_default().push(RefInfo {
-                        name: short_name,
-                        kind: kind.to_string(),
-                    });
-
-                    relevant_oids.insert(target_oid);
-                }
-            }
-        }
-    }
-
-    // Check HEAD
-    if let Ok(head) = repo.head() {
-        if let Some(target) = head.target() {
-             let target_oid = target.to_string();
-             ref_map.entry(target_oid.clone()).or_default().push(RefInfo {
-                name: "HEAD".to_string(),
-                kind: "HEAD".to_string(),
-            });
-            relevant_oids.insert(target_oid);
-        }
-    }
+    let ref_map = build_ref_map(&repo);
 
     let mut revwalk = repo.revwalk().map_err(|e| e.to_string())?;
     revwalk.set_sorting(Sort::TOPOLOGICAL | Sort::TIME).map_err(|e| e.to_string())?;
@@ -88,36 +116,776 @@ pub fn get_commits(path: String) -> Result<Vec<CommitInfo>, String> {
         let oid = oid.map_err(|e| e.to_string())?;
         let commit = repo.find_commit(oid).map_err(|e| e.to_string())?;
 
-        let parents: Vec<String> = commit.parent_ids().map(|p| p.to_string()).collect();
+        let refs = ref_map.get(&oid.to_string()).cloned().unwrap_or_default();
+        let info = build_commit_info(&repo, &commit, refs);
 
-        let info = CommitInfo {
-            oid: oid.to_string(),
-            parents: parents.clone(),
-            author: commit.author().name().unwrap_or("Unknown").to_string(),
-            email: commit.author().email().unwrap_or("").to_string(),
-            date: commit.time().seconds(),
-            message: commit.message().unwrap_or("").to_string(),
-            refs: ref_map.get(&oid.to_string()).cloned().unwrap_or_default(),
+        all_commits.push(info);
+    }
+
+    let mut simplified_commits = simplify_commits(&all_commits);
+
+    // If simplified list is empty (e.g. no refs, no merges), just return everything?
+    // Or if the user has a repo with just one commit and no refs (unlikely).
+    if simplified_commits.is_empty() && !all_commits.is_empty() {
+        assign_lanes(&mut all_commits);
+        return Ok(all_commits);
+    }
+
+    assign_lanes(&mut simplified_commits);
+    Ok(simplified_commits)
+}
+
+/// One page of the commit graph plus the frontier OIDs to resume from.
+#[derive(Serialize, Clone, Debug)]
+pub struct GraphPage {
+    pub commits: Vec<CommitInfo>,
+    pub frontier: Vec<String>,
+}
+
+/// Per-repo pagination cursor: the set of OIDs already emitted across pages so
+/// resumed walks skip them. Keyed by repo path, guarded by a Mutex. Managed by
+/// Tauri.
+#[derive(Default)]
+pub struct PaginationState {
+    cursors: std::sync::Mutex<
+        std::collections::HashMap<String, std::collections::HashSet<String>>,
+    >,
+}
+
+/// Cursor-based, paginated alternative to [`get_commits`], which returns the
+/// whole history in one call.
+///
+/// On the first call (`start_oids` is `None`) the walk starts from all refs;
+/// subsequent calls push the `frontier` returned by the previous page, so
+/// commits above the frontier are not walked again. The revwalk is sorted
+/// (topological and by time), so libgit2 still reads every commit reachable
+/// from the pushed tips before yielding the first one; `limit` bounds only how
+/// many commits are built and returned. The already-emitted
+/// OIDs are remembered per repo path so resumed walks don't repeat commits, and
+/// the existing ancestor-simplification is applied per page (parents outside the
+/// page are kept verbatim to stitch pages together).
+#[tauri::command]
+pub fn get_commits_page(
+    state: tauri::State<'_, PaginationState>,
+    path: String,
+    start_oids: Option<Vec<String>>,
+    limit: usize,
+) -> Result<GraphPage, String> {
+    let repo = Repository::open(&path).map_err(|e| e.to_string())?;
+    let ref_map = build_ref_map(&repo);
+
+    let mut cursors = state.cursors.lock().map_err(|e| e.to_string())?;
+    let seen = cursors.entry(path.clone()).or_default();
+    // A call with no tips is a fresh start: forget previously-emitted commits.
+    if start_oids.is_none() {
+        seen.clear();
+    }
+
+    let mut revwalk = repo.revwalk().map_err(|e| e.to_string())?;
+    revwalk
+        .set_sorting(Sort::TOPOLOGICAL | Sort::TIME)
+        .map_err(|e| e.to_string())?;
+    match &start_oids {
+        Some(tips) => {
+            for tip in tips {
+                if let Ok(oid) = Oid::from_str(tip) {
+                    let _ = revwalk.push(oid);
+                }
+            }
+        }
+        None => {
+            let _ = revwalk.push_glob("refs/heads/*");
+            let _ = revwalk.push_glob("refs/tags/*");
+            let _ = revwalk.push_glob("refs/remotes/*");
+            let _ = revwalk.push_head();
+        }
+    }
+
+    // Walk up to `limit` not-yet-emitted commits.
+    let mut page = Vec::new();
+    for oid in revwalk {
+        if page.len() >= limit {
+            break;
+        }
+        let oid = oid.map_err(|e| e.to_string())?;
+        let key = oid.to_string();
+        if seen.contains(&key) {
+            continue;
+        }
+        let commit = repo.find_commit(oid).map_err(|e| e.to_string())?;
+        let refs = ref_map.get(&key).cloned().unwrap_or_default();
+        page.push(build_commit_info(&repo, &commit, refs));
+        seen.insert(key);
+    }
+
+    // Frontier = parents of this page not yet emitted; resume from them.
+    let mut frontier: Vec<String> = Vec::new();
+    for commit in &page {
+        for parent in &commit.parents {
+            if !seen.contains(parent) && !frontier.contains(parent) {
+                frontier.push(parent.clone());
+            }
+        }
+    }
+
+    let mut commits = simplify_commits(&page);
+    if commits.is_empty() && !page.is_empty() {
+        commits = page;
+    }
+    assign_lanes(&mut commits);
+
+    Ok(GraphPage { commits, frontier })
+}
+
+#[tauri::command]
+pub fn checkout_ref(path: String, reference: String) -> Result<(), String> {
+    let repo = Repository::open(&path).map_err(|e| e.to_string())?;
+
+    // Try to find the ref (branch/tag) or commit
+    // If reference is a branch name like "main", we want to checkout that branch
+    // If it's a commit hash, we checkout detached
+
+    // First try to resolve as a reference
+    let (object, ref_obj) = match repo.revparse_ext(&reference) {
+        Ok(res) => res,
+        Err(_) => return Err(format!("Reference not found: {}", reference)),
+    };
+
+    // Checkout the tree
+    repo.checkout_tree(&object, None).map_err(|e| e.to_string())?;
+
+    match ref_obj {
+        Some(gref) => {
+            // It's a reference (branch/tag)
+            if gref.is_branch() {
+                 repo.set_head(gref.name().unwrap()).map_err(|e| e.to_string())?;
+            } else {
+                 repo.set_head_detached(object.id()).map_err(|e| e.to_string())?;
+            }
+        },
+        None => {
+            // It's a commit ID
+            repo.set_head_detached(object.id()).map_err(|e| e.to_string())?;
+        }
+    }
+
+    Ok(())
+}
+
+#[tauri::command]
+pub fn get_commit_changes(
+    path: String,
+    oid: String,
+    include_patch: Option<bool>,
+) -> Result<Vec<FileChange>, String> {
+    let repo = Repository::open(&path).map_err(|e| e.to_string())?;
+    let commit = repo.find_commit(Oid::from_str(&oid).map_err(|e| e.to_string())?).map_err(|e| e.to_string())?;
+    let tree = commit.tree().map_err(|e| e.to_string())?;
+
+    let parent_tree = if commit.parent_count() > 0 {
+        Some(commit.parent(0).map_err(|e| e.to_string())?.tree().map_err(|e| e.to_string())?)
+    } else {
+        None
+    };
+
+    let mut opts = diff_options(&None, None);
+    let mut diff = repo
+        .diff_tree_to_tree(parent_tree.as_ref(), Some(&tree), Some(&mut opts))
+        .map_err(|e| e.to_string())?;
+    detect_renames(&mut diff)?;
+
+    collect_changes(&diff, include_patch.unwrap_or(false))
+}
+
+#[tauri::command]
+pub fn compare_commits(
+    path: String,
+    oid1: String,
+    oid2: String,
+    include_patch: Option<bool>,
+) -> Result<Vec<FileChange>, String> {
+    let repo = Repository::open(&path).map_err(|e| e.to_string())?;
+    let commit1 = repo.find_commit(Oid::from_str(&oid1).map_err(|e| e.to_string())?).map_err(|e| e.to_string())?;
+    let commit2 = repo.find_commit(Oid::from_str(&oid2).map_err(|e| e.to_string())?).map_err(|e| e.to_string())?;
+
+    let tree1 = commit1.tree().map_err(|e| e.to_string())?;
+    let tree2 = commit2.tree().map_err(|e| e.to_string())?;
+
+    let mut opts = diff_options(&None, None);
+    let mut diff = repo
+        .diff_tree_to_tree(Some(&tree1), Some(&tree2), Some(&mut opts))
+        .map_err(|e| e.to_string())?;
+    detect_renames(&mut diff)?;
+
+    collect_changes(&diff, include_patch.unwrap_or(false))
+}
+
+/// Return the structured hunks for a single file in a commit's diff against its
+/// first parent, honouring the supplied context-line and whitespace options.
+#[tauri::command]
+pub fn get_file_diff(
+    path: String,
+    oid: String,
+    file: String,
+    options: Option<DiffConfig>,
+) -> Result<Vec<DiffHunk>, String> {
+    let repo = Repository::open(&path).map_err(|e| e.to_string())?;
+    let commit = repo.find_commit(Oid::from_str(&oid).map_err(|e| e.to_string())?).map_err(|e| e.to_string())?;
+    let tree = commit.tree().map_err(|e| e.to_string())?;
+
+    let parent_tree = if commit.parent_count() > 0 {
+        Some(commit.parent(0).map_err(|e| e.to_string())?.tree().map_err(|e| e.to_string())?)
+    } else {
+        None
+    };
+
+    // Run the diff (and rename detection) unrestricted: a pathspec would drop
+    // the deletion of the old path, so `find_similar` would miss the rename and
+    // report the file as Added. Filter to the requested file afterwards.
+    let mut opts = diff_options(&options, None);
+    let mut diff = repo
+        .diff_tree_to_tree(parent_tree.as_ref(), Some(&tree), Some(&mut opts))
+        .map_err(|e| e.to_string())?;
+    detect_renames(&mut diff)?;
+
+    let changes = collect_changes(&diff, true)?;
+    Ok(changes
+        .into_iter()
+        .find(|c| c.path == file)
+        .and_then(|c| c.hunks)
+        .unwrap_or_default())
+}
+
+/// Build [`git2::DiffOptions`] from a [`DiffConfig`], optionally restricted to a
+/// single pathspec.
+fn diff_options(config: &Option<DiffConfig>, pathspec: Option<&str>) -> git2::DiffOptions {
+    let mut opts = git2::DiffOptions::new();
+    if let Some(config) = config {
+        if let Some(context) = config.context_lines {
+            opts.context_lines(context);
+        }
+        if config.ignore_whitespace.unwrap_or(false) {
+            opts.ignore_whitespace(true);
+        }
+    }
+    if let Some(pathspec) = pathspec {
+        opts.pathspec(pathspec);
+    }
+    opts
+}
+
+/// Run rename/copy detection so moved files show up as renames.
+fn detect_renames(diff: &mut git2::Diff) -> Result<(), String> {
+    let mut find_options = git2::DiffFindOptions::new();
+    find_options.renames(true).copies(true);
+    diff.find_similar(Some(&mut find_options))
+        .map_err(|e| e.to_string())
+}
+
+/// Collect per-file changes from a diff, attaching structured hunks when
+/// `include_patch` is set.
+fn collect_changes(diff: &git2::Diff, include_patch: bool) -> Result<Vec<FileChange>, String> {
+    // RefCell lets the file/hunk/line callbacks all mutate the same vector;
+    // `foreach` invokes them in order (file, then its hunks and lines).
+    let changes: std::cell::RefCell<Vec<FileChange>> = std::cell::RefCell::new(Vec::new());
+
+    let mut file_cb = |delta: git2::DiffDelta, _progress: f32| {
+        let path = delta
+            .new_file()
+            .path()
+            .or_else(|| delta.old_file().path())
+            .map(|p| p.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let old_path = match delta.status() {
+            git2::Delta::Renamed | git2::Delta::Copied => delta
+                .old_file()
+                .path()
+                .map(|p| p.to_string_lossy().to_string()),
+            _ => None,
         };
+        changes.borrow_mut().push(FileChange {
+            path,
+            status: format!("{:?}", delta.status()),
+            old_path,
+            hunks: if include_patch { Some(Vec::new()) } else { None },
+        });
+        true
+    };
 
-        all_commits.push(info);
+    let mut hunk_cb = |_delta: git2::DiffDelta, hunk: git2::DiffHunk| {
+        if let Some(file) = changes.borrow_mut().last_mut() {
+            if let Some(hunks) = file.hunks.as_mut() {
+                hunks.push(DiffHunk {
+                    header: String::from_utf8_lossy(hunk.header()).to_string(),
+                    old_start: hunk.old_start(),
+                    old_lines: hunk.old_lines(),
+                    new_start: hunk.new_start(),
+                    new_lines: hunk.new_lines(),
+                    lines: Vec::new(),
+                });
+            }
+        }
+        true
+    };
+
+    let mut line_cb = |_delta: git2::DiffDelta, _hunk: Option<git2::DiffHunk>, line: git2::DiffLine| {
+        if let Some(file) = changes.borrow_mut().last_mut() {
+            if let Some(hunk) = file.hunks.as_mut().and_then(|h| h.last_mut()) {
+                hunk.lines.push(diff_line(&line));
+            }
+        }
+        true
+    };
 
-        if all_commits.len() >= 2000 { break; }
+    if include_patch {
+        diff.foreach(&mut file_cb, None, Some(&mut hunk_cb), Some(&mut line_cb))
+            .map_err(|e| e.to_string())?;
+    } else {
+        diff.foreach(&mut file_cb, None, None, None)
+            .map_err(|e| e.to_string())?;
     }
 
-    // Now we have a list of commits (topologically sorted).
-    // We want to filter `all_commits` to only include those that are "interesting" (have refs)
-    // AND re-link their parents to the nearest interesting ancestor.
+    Ok(changes.into_inner())
+}
+
+fn diff_line(line: &git2::DiffLine) -> DiffLine {
+    let origin = match line.origin() {
+        '+' => "Addition",
+        '-' => "Deletion",
+        _ => "Context",
+    };
+    DiffLine {
+        origin: origin.to_string(),
+        old_lineno: line.old_lineno(),
+        new_lineno: line.new_lineno(),
+        content: String::from_utf8_lossy(line.content()).to_string(),
+    }
+}
+
+/// Credentials supplied by the frontend for authenticated remote operations.
+///
+/// All fields are optional: when the remote accepts SSH keys the credential
+/// callback makes one SSH attempt, with the on-disk SSH key if one is given
+/// (with optional public key and passphrase) and otherwise with the SSH agent;
+/// the agent is not tried after a rejected key file. Username/password is used
+/// when the remote asks for plaintext auth.
+#[derive(Deserialize, Default, Clone)]
+pub struct GitCredentials {
+    pub username: Option<String>,
+    pub password: Option<String>,
+    pub ssh_key: Option<String>,
+    pub ssh_public_key: Option<String>,
+    pub ssh_passphrase: Option<String>,
+}
+
+/// Payload for the `clone://progress` event, mirroring `git2::Progress`.
+#[derive(Serialize, Clone)]
+pub struct TransferProgress {
+    pub received_objects: usize,
+    pub indexed_objects: usize,
+    pub total_objects: usize,
+    pub received_bytes: usize,
+}
+
+/// Build remote callbacks wired for authentication and transfer progress.
+///
+/// The returned callbacks own their captured state (`'static`) so they can be
+/// handed to [`git2::FetchOptions`] without borrow juggling.
+fn remote_callbacks(
+    app: tauri::AppHandle,
+    creds: GitCredentials,
+    event: &'static str,
+) -> git2::RemoteCallbacks<'static> {
+    use git2::{Cred, CredentialType};
+
+    let mut callbacks = git2::RemoteCallbacks::new();
+
+    // libgit2 re-invokes this callback after an auth failure, so offering the
+    // same credential again would spin forever against a wrong key/agent. Track
+    // what's already been tried and give up once it's been rejected.
+    let mut tried_ssh = false;
+    let mut tried_userpass = false;
+    callbacks.credentials(move |_url, username_from_url, allowed| {
+        let user = username_from_url.unwrap_or("git");
+        if allowed.contains(CredentialType::SSH_KEY) && !tried_ssh {
+            tried_ssh = true;
+            if let Some(key) = &creds.ssh_key {
+                return Cred::ssh_key(
+                    user,
+                    creds.ssh_public_key.as_deref().map(Path::new),
+                    Path::new(key),
+                    creds.ssh_passphrase.as_deref(),
+                );
+            }
+            return Cred::ssh_key_from_agent(user);
+        }
+        if allowed.contains(CredentialType::USER_PASS_PLAINTEXT) && !tried_userpass {
+            tried_userpass = true;
+            if let (Some(u), Some(p)) = (&creds.username, &creds.password) {
+                return Cred::userpass_plaintext(u, p);
+            }
+        }
+        Err(git2::Error::from_str("authentication failed: no untried credentials available"))
+    });
+
+    callbacks.transfer_progress(move |stats| {
+        let _ = app.emit(
+            event,
+            TransferProgress {
+                received_objects: stats.received_objects(),
+                indexed_objects: stats.indexed_objects(),
+                total_objects: stats.total_objects(),
+                received_bytes: stats.received_bytes(),
+            },
+        );
+        true
+    });
+
+    callbacks
+}
+
+#[tauri::command]
+pub fn clone_repo(
+    app: tauri::AppHandle,
+    url: String,
+    path: String,
+    credentials: Option<GitCredentials>,
+) -> Result<String, String> {
+    let creds = credentials.unwrap_or_default();
+
+    let mut fetch_options = git2::FetchOptions::new();
+    fetch_options.remote_callbacks(remote_callbacks(app, creds, "clone://progress"));
+
+    let mut builder = git2::build::RepoBuilder::new();
+    builder.fetch_options(fetch_options);
+    builder
+        .clone(&url, Path::new(&path))
+        .map_err(|e| e.to_string())?;
 
-    // Map OID -> CommitInfo
-    let mut commit_map: std::collections::HashMap<String, CommitInfo> = std::collections::HashMap::new();
-    for c in &all_commits {
+    Ok("Cloned successfully".to_string())
+}
+
+#[tauri::command]
+pub fn fetch_remote(
+    app: tauri::AppHandle,
+    path: String,
+    remote: String,
+    credentials: Option<GitCredentials>,
+) -> Result<String, String> {
+    let creds = credentials.unwrap_or_default();
+    let repo = Repository::open(&path).map_err(|e| e.to_string())?;
+    let mut remote = repo.find_remote(&remote).map_err(|e| e.to_string())?;
+
+    let mut fetch_options = git2::FetchOptions::new();
+    fetch_options.remote_callbacks(remote_callbacks(app, creds, "clone://progress"));
+
+    let refspecs: Vec<String> = remote
+        .fetch_refspecs()
+        .map_err(|e| e.to_string())?
+        .iter()
+        .flatten()
+        .map(|s| s.to_string())
+        .collect();
+    remote
+        .fetch(&refspecs, Some(&mut fetch_options), None)
+        .map_err(|e| e.to_string())?;
+
+    Ok("Fetched successfully".to_string())
+}
+
+#[tauri::command]
+pub fn pull_ref(
+    app: tauri::AppHandle,
+    path: String,
+    remote: String,
+    branch: String,
+    credentials: Option<GitCredentials>,
+) -> Result<String, String> {
+    let creds = credentials.unwrap_or_default();
+    let repo = Repository::open(&path).map_err(|e| e.to_string())?;
+
+    {
+        let mut remote = repo.find_remote(&remote).map_err(|e| e.to_string())?;
+        let mut fetch_options = git2::FetchOptions::new();
+        fetch_options.remote_callbacks(remote_callbacks(app, creds, "clone://progress"));
+        remote
+            .fetch(&[&branch], Some(&mut fetch_options), None)
+            .map_err(|e| e.to_string())?;
+    }
+
+    let fetch_head = repo
+        .find_reference("FETCH_HEAD")
+        .map_err(|e| e.to_string())?;
+    let fetch_commit = repo
+        .reference_to_annotated_commit(&fetch_head)
+        .map_err(|e| e.to_string())?;
+
+    let (analysis, _) = repo.merge_analysis(&[&fetch_commit]).map_err(|e| e.to_string())?;
+
+    if analysis.is_up_to_date() {
+        return Ok("Already up to date".to_string());
+    }
+
+    if analysis.is_fast_forward() {
+        let refname = format!("refs/heads/{branch}");
+        let mut reference = repo.find_reference(&refname).map_err(|e| e.to_string())?;
+        reference
+            .set_target(fetch_commit.id(), "pull: fast-forward")
+            .map_err(|e| e.to_string())?;
+        repo.set_head(&refname).map_err(|e| e.to_string())?;
+        repo.checkout_head(Some(git2::build::CheckoutBuilder::default().force()))
+            .map_err(|e| e.to_string())?;
+        return Ok("Fast-forwarded".to_string());
+    }
+
+    // Non-fast-forward: perform a real merge of the fetched commit into HEAD.
+    let mut merge_options = git2::MergeOptions::new();
+    repo.merge(
+        &[&fetch_commit],
+        Some(&mut merge_options),
+        Some(git2::build::CheckoutBuilder::default().force()),
+    )
+    .map_err(|e| e.to_string())?;
+
+    if repo.index().map_err(|e| e.to_string())?.has_conflicts() {
+        // Don't leave the repo in a half-finished MERGING state: reset the index
+        // and working tree back to HEAD and clear MERGE_HEAD before bailing.
+        let head_commit = repo
+            .head()
+            .and_then(|h| h.peel_to_commit())
+            .map_err(|e| e.to_string())?;
+        repo.reset(head_commit.as_object(), git2::ResetType::Hard, None)
+            .map_err(|e| e.to_string())?;
+        repo.cleanup_state().map_err(|e| e.to_string())?;
+        return Err("Merge produced conflicts; resolve them manually".to_string());
+    }
+
+    // Conflict-free: write the merged index out as a proper merge commit with
+    // both tips as parents, then clear the MERGING state.
+    let mut index = repo.index().map_err(|e| e.to_string())?;
+    let tree_id = index.write_tree().map_err(|e| e.to_string())?;
+    let tree = repo.find_tree(tree_id).map_err(|e| e.to_string())?;
+    let signature = repo.signature().map_err(|e| e.to_string())?;
+    let head_commit = repo
+        .head()
+        .and_then(|h| h.peel_to_commit())
+        .map_err(|e| e.to_string())?;
+    let merged_commit = repo
+        .find_commit(fetch_commit.id())
+        .map_err(|e| e.to_string())?;
+    let message = format!("Merge {} into {branch}", fetch_commit.id());
+    repo.commit(
+        Some("HEAD"),
+        &signature,
+        &signature,
+        &message,
+        &tree,
+        &[&head_commit, &merged_commit],
+    )
+    .map_err(|e| e.to_string())?;
+    repo.cleanup_state().map_err(|e| e.to_string())?;
+    repo.checkout_head(Some(git2::build::CheckoutBuilder::default().force()))
+        .map_err(|e| e.to_string())?;
+
+    Ok("Merged".to_string())
+}
+
+#[tauri::command]
+pub fn push_ref(
+    app: tauri::AppHandle,
+    path: String,
+    remote: String,
+    refspec: String,
+    credentials: Option<GitCredentials>,
+) -> Result<String, String> {
+    use std::cell::RefCell;
+
+    let creds = credentials.unwrap_or_default();
+    let repo = Repository::open(&path).map_err(|e| e.to_string())?;
+    let mut remote = repo.find_remote(&remote).map_err(|e| e.to_string())?;
+
+    // `push_update_reference` fires once per pushed ref with the server's
+    // rejection reason, if any. Collect them to report back to the frontend.
+    let rejected: RefCell<Vec<String>> = RefCell::new(Vec::new());
+    let mut callbacks = remote_callbacks(app, creds, "clone://progress");
+    callbacks.push_update_reference(|refname, status| {
+        if let Some(msg) = status {
+            rejected.borrow_mut().push(format!("{refname}: {msg}"));
+        }
+        Ok(())
+    });
+
+    let mut push_options = git2::PushOptions::new();
+    push_options.remote_callbacks(callbacks);
+
+    remote
+        .push(&[&refspec], Some(&mut push_options))
+        .map_err(|e| e.to_string())?;
+    // The callbacks inside `push_options` borrow `rejected`; release them first.
+    drop(push_options);
+
+    let rejected = rejected.into_inner();
+    if rejected.is_empty() {
+        Ok("Pushed successfully".to_string())
+    } else {
+        Err(format!("Rejected refs: {}", rejected.join(", ")))
+    }
+}
+
+/// Build a [`CommitInfo`] from a commit, attaching the given refs and detecting
+/// signature presence cheaply (full trust verification is done on demand by
+/// [`verify_commit`]).
+fn build_commit_info(repo: &Repository, commit: &git2::Commit, refs: Vec<RefInfo>) -> CommitInfo {
+    let oid = commit.id();
+    let is_merge = commit.parent_count() > 1;
+    let trivial_merge = is_merge && is_trivial_merge(commit);
+
+    let signature = if repo.extract_signature(&oid, None).is_ok() {
+        CommitSignature::Unverified
+    } else {
+        CommitSignature::Unsigned
+    };
+
+    CommitInfo {
+        oid: oid.to_string(),
+        parents: commit.parent_ids().map(|p| p.to_string()).collect(),
+        author: commit.author().name().unwrap_or("Unknown").to_string(),
+        email: commit.author().email().unwrap_or("").to_string(),
+        date: commit.time().seconds(),
+        message: commit.message().unwrap_or("").to_string(),
+        refs,
+        is_merge,
+        trivial_merge,
+        signature,
+        lane: 0,
+        edges: Vec::new(),
+    }
+}
+
+/// Assign each commit a lane (column) and the edge segments crossing its row,
+/// so a canvas renderer can draw the DAG without re-deriving layout.
+///
+/// Commits must be in topological order (children before parents). We keep a
+/// vector of "active lanes", each holding the OID it currently expects next.
+/// For each commit we take the leftmost lane expecting it (allocating one if
+/// none does), collapse any other lanes expecting the same OID into it (merge),
+/// then point its lane at the first parent and allocate lanes for the rest
+/// (branch). Freed lanes are reused before the vector grows, which keeps lane
+/// indices compact.
+fn assign_lanes(commits: &mut [CommitInfo]) {
+    let mut lanes: Vec<Option<String>> = Vec::new();
+
+    for commit in commits.iter_mut() {
+        let oid = commit.oid.clone();
+
+        // Leftmost active lane already expecting this commit, else a fresh lane.
+        let my_lane = match lanes.iter().position(|slot| slot.as_deref() == Some(oid.as_str())) {
+            Some(i) => i,
+            None => allocate_lane(&mut lanes),
+        };
+
+        // Any other lane expecting this OID is a merge edge collapsing inward.
+        let mut edges: Vec<GraphEdge> = Vec::new();
+        for (i, slot) in lanes.iter_mut().enumerate() {
+            if i != my_lane && slot.as_deref() == Some(oid.as_str()) {
+                edges.push(GraphEdge { from: i, to: my_lane });
+                *slot = None;
+            }
+        }
+
+        // This lane now follows the first parent; extra parents branch off.
+        match commit.parents.first() {
+            Some(first) => lanes[my_lane] = Some(first.clone()),
+            None => lanes[my_lane] = None,
+        }
+        for parent in commit.parents.iter().skip(1) {
+            if lanes.iter().any(|slot| slot.as_deref() == Some(parent.as_str())) {
+                continue;
+            }
+            let lane = allocate_lane(&mut lanes);
+            lanes[lane] = Some(parent.clone());
+        }
+
+        // Lanes still expecting a commit continue straight past this row.
+        for (i, slot) in lanes.iter().enumerate() {
+            if slot.is_some() {
+                edges.push(GraphEdge { from: i, to: i });
+            }
+        }
+
+        // Drop trailing empty lanes so the active width stays bounded.
+        while matches!(lanes.last(), Some(None)) {
+            lanes.pop();
+        }
+
+        commit.lane = my_lane;
+        commit.edges = edges;
+    }
+}
+
+/// Reuse the leftmost freed lane if one exists, otherwise append a new one.
+fn allocate_lane(lanes: &mut Vec<Option<String>>) -> usize {
+    match lanes.iter().position(|slot| slot.is_none()) {
+        Some(i) => i,
+        None => {
+            lanes.push(None);
+            lanes.len() - 1
+        }
+    }
+}
+
+/// Map every ref target (plus HEAD) to the refs that point at it.
+fn build_ref_map(repo: &Repository) -> std::collections::HashMap<String, Vec<RefInfo>> {
+    let mut ref_map: std::collections::HashMap<String, Vec<RefInfo>> =
+        std::collections::HashMap::new();
+
+    if let Ok(references) = repo.references() {
+        for r in references.flatten() {
+            if let (Some(name), Some(target)) = (r.name(), r.target()) {
+                let kind = if r.is_remote() {
+                    "remote"
+                } else if r.is_tag() {
+                    "tag"
+                } else if r.is_branch() {
+                    "branch"
+                } else {
+                    "other"
+                };
+                let short_name = r.shorthand().unwrap_or(name).to_string();
+                ref_map.entry(target.to_string()).or_default().push(RefInfo {
+                    name: short_name,
+                    kind: kind.to_string(),
+                });
+            }
+        }
+    }
+
+    if let Ok(head) = repo.head() {
+        if let Some(target) = head.target() {
+            ref_map.entry(target.to_string()).or_default().push(RefInfo {
+                name: "HEAD".to_string(),
+                kind: "HEAD".to_string(),
+            });
+        }
+    }
+
+    ref_map
+}
+
+/// Filter a topo-sorted window of commits to the "interesting" ones (carrying
+/// refs, merges, or roots) and re-link each kept commit's parents to the nearest
+/// interesting ancestor within the window. Parents that fall outside the window
+/// are kept verbatim so callers can stitch successive pages together.
+fn simplify_commits(all_commits: &[CommitInfo]) -> Vec<CommitInfo> {
+    let mut commit_map: std::collections::HashMap<String, CommitInfo> =
+        std::collections::HashMap::new();
+    for c in all_commits {
         commit_map.insert(c.oid.clone(), c.clone());
     }
 
     let mut simplified_commits = Vec::new();
 
-    for commit in &all_commits {
+    for commit in all_commits {
         // Keep if it has refs OR is a merge commit (optional, but good for graph) OR is the very first commit
         let has_refs = !commit.refs.is_empty();
         let is_merge = commit.parents.len() > 1;
@@ -154,121 +922,465 @@ pub fn get_commits(path: String) -> Result<Vec<CommitInfo>, String> {
                             }
                         }
                     } else {
-                        // Ancestor not in our loaded list (maybe beyond 2000 limit)
+                        // Ancestor not in our loaded window (e.g. a later page)
                         // Just keep the link to the edge
                         new_parents.push(runner);
                         break;
                     }
                 }
             }
-            // Deduplicate parents
-            new_parents.sort();
-            new_parents.dedup();
+            // Deduplicate parents while preserving order: the lane pass relies
+            // on `parents.first()` being the real mainline parent, so a lexical
+            // sort here would mislabel merge mainlines.
+            let mut seen_parents = std::collections::HashSet::new();
+            new_parents.retain(|p| seen_parents.insert(p.clone()));
 
             new_commit.parents = new_parents;
             simplified_commits.push(new_commit);
         }
     }
 
-    // If simplified list is empty (e.g. no refs, no merges), just return everything?
-    // Or if the user has a repo with just one commit and no refs (unlikely).
-    if simplified_commits.is_empty() && !all_commits.is_empty() {
-        return Ok(all_commits);
-    }
+    simplified_commits
+}
 
-    Ok(simplified_commits)
+/// A merge is trivial when its tree matches one of its parents verbatim, i.e.
+/// the merge commit itself introduced no changes.
+fn is_trivial_merge(commit: &git2::Commit) -> bool {
+    let tree_id = match commit.tree() {
+        Ok(t) => t.id(),
+        Err(_) => return false,
+    };
+    commit.parents().any(|p| matches!(p.tree(), Ok(t) if t.id() == tree_id))
 }
 
+/// Verify a commit's signature against a trusted keyring.
+///
+/// `keyring_path` points at an exported set of trusted public keys — an ASCII
+/// or binary GPG keyring for GPG-signed commits, or an OpenSSH `allowed_signers`
+/// file for SSH-signed ones. The commit's embedded signer is matched against the
+/// keyring to decide between [`CommitSignature::SignedValid`] (trusted signer),
+/// [`CommitSignature::SignedUntrusted`] (intact signature, key not trusted) and
+/// [`CommitSignature::SignedBad`] (signature does not verify).
 #[tauri::command]
-pub fn checkout_ref(path: String, reference: String) -> Result<(), String> {
+pub fn verify_commit(
+    path: String,
+    oid: String,
+    keyring_path: String,
+) -> Result<CommitSignature, String> {
     let repo = Repository::open(&path).map_err(|e| e.to_string())?;
+    let oid = Oid::from_str(&oid).map_err(|e| e.to_string())?;
 
-    // Try to find the ref (branch/tag) or commit
-    // If reference is a branch name like "main", we want to checkout that branch
-    // If it's a commit hash, we checkout detached
+    let (sig, payload) = match repo.extract_signature(&oid, None) {
+        Ok(pair) => pair,
+        Err(_) => return Ok(CommitSignature::Unsigned),
+    };
 
-    // First try to resolve as a reference
-    let (object, ref_obj) = match repo.revparse_ext(&reference) {
-        Ok(res) => res,
-        Err(_) => return Err(format!("Reference not found: {}", reference)),
+    if sig.as_ref().starts_with(b"-----BEGIN SSH SIGNATURE-----") {
+        verify_ssh_signature(&oid.to_string(), &keyring_path, sig.as_ref(), payload.as_ref())
+    } else {
+        verify_gpg_signature(&oid.to_string(), &keyring_path, sig.as_ref(), payload.as_ref())
+    }
+}
+
+fn verify_gpg_signature(
+    oid: &str,
+    keyring_path: &str,
+    sig: &[u8],
+    payload: &[u8],
+) -> Result<CommitSignature, String> {
+    use gpgme::SignatureSummary;
+
+    // Verification must not touch the operator's real `~/.gnupg`, so point the
+    // engine at a throwaway home dir and import the trusted keyring there. The
+    // imported keys (and any gpg-agent state) are discarded with the directory.
+    let home = std::env::temp_dir().join(format!("git-tree-gpg-{}-{oid}", std::process::id()));
+    std::fs::create_dir_all(&home).map_err(|e| e.to_string())?;
+    let cleanup = || {
+        let _ = std::fs::remove_dir_all(&home);
     };
 
-    // Checkout the tree
-    repo.checkout_tree(&object, None).map_err(|e| e.to_string())?;
+    let mut ctx =
+        gpgme::Context::from_protocol(gpgme::Protocol::OpenPgp).map_err(|e| e.to_string())?;
+    if let Err(e) = ctx.set_engine_home_dir(home.to_string_lossy().as_bytes()) {
+        cleanup();
+        return Err(e.to_string());
+    }
 
-    match ref_obj {
-        Some(gref) => {
-            // It's a reference (branch/tag)
-            if gref.is_branch() {
-                 repo.set_head(gref.name().unwrap()).map_err(|e| e.to_string())?;
-            } else {
-                 repo.set_head_detached(object.id()).map_err(|e| e.to_string())?;
-            }
-        },
+    // Import the user-supplied trusted public keys into the ephemeral keyring.
+    let key_data = match std::fs::read(keyring_path) {
+        Ok(data) => data,
+        Err(e) => {
+            cleanup();
+            return Err(e.to_string());
+        }
+    };
+    if let Err(e) = ctx.import(key_data) {
+        cleanup();
+        return Err(e.to_string());
+    }
+
+    let result = match ctx.verify_detached(sig, payload) {
+        Ok(r) => r,
+        Err(e) => {
+            cleanup();
+            return Err(e.to_string());
+        }
+    };
+    let signature = match result.signatures().next() {
+        Some(s) => s,
         None => {
-            // It's a commit ID
-            repo.set_head_detached(object.id()).map_err(|e| e.to_string())?;
+            cleanup();
+            return Ok(CommitSignature::SignedBad);
         }
+    };
+
+    // Trust is membership-based, independent of the host gpg trust-db: a key the
+    // signature resolves to in our ephemeral keyring is, by construction, one of
+    // the supplied trusted keys.
+    let signer_key = signature.fingerprint().ok().and_then(|fpr| ctx.get_key(fpr).ok());
+    let in_keyring = signer_key.is_some();
+    let signer = signer_key
+        .as_ref()
+        .and_then(|key| {
+            key.user_ids()
+                .next()
+                .and_then(|u| u.email().ok().map(|e| e.to_string()))
+        })
+        .or_else(|| signature.fingerprint().ok().map(|f| f.to_string()))
+        .unwrap_or_else(|| "unknown".to_string());
+    let summary = signature.summary();
+
+    cleanup();
+
+    // Reserve the "bad/tampered" badge for a genuinely invalid signature (RED).
+    // A key that isn't in the ephemeral keyring fails with KEY_MISSING /
+    // GPG_ERR_NO_PUBKEY — that's an untrusted signer, not a bad signature.
+    if summary.contains(SignatureSummary::RED) {
+        Ok(CommitSignature::SignedBad)
+    } else if in_keyring && !summary.contains(SignatureSummary::KEY_MISSING) {
+        Ok(CommitSignature::SignedValid { signer })
+    } else {
+        // Cryptographically intact, but the signer is not in the keyring.
+        Ok(CommitSignature::SignedUntrusted { signer })
     }
+}
+
+/// Tracks the stop-flags of every live [`watch_repo`] task, keyed by repo path,
+/// so [`stop_watch`] can tear an individual watcher down. Managed by Tauri.
+#[derive(Default)]
+pub struct WatcherState {
+    watchers: std::sync::Mutex<
+        std::collections::HashMap<String, std::sync::Arc<std::sync::atomic::AtomicBool>>,
+    >,
+}
+
+/// Spawn a background task that polls the repo's refs and emits a
+/// `watch://update` event with the batch of newly-reachable commits whenever
+/// HEAD, a branch, or a remote-tracking ref moves.
+///
+/// Only the commits made reachable by the move are walked (the previous tips
+/// are hidden from the revwalk), so the frontend receives an incremental batch
+/// rather than re-running the full [`get_commits`].
+#[tauri::command]
+pub fn watch_repo(
+    app: tauri::AppHandle,
+    state: tauri::State<'_, WatcherState>,
+    path: String,
+) -> Result<(), String> {
+    use std::sync::atomic::Ordering;
+
+    let mut watchers = state.watchers.lock().map_err(|e| e.to_string())?;
+    if watchers.contains_key(&path) {
+        return Ok(());
+    }
+    let stop = std::sync::Arc::new(std::sync::atomic::AtomicBool::new(false));
+    watchers.insert(path.clone(), stop.clone());
+    drop(watchers);
+
+    std::thread::spawn(move || {
+        let mut previous = ref_snapshot(&path);
+        while !stop.load(Ordering::Relaxed) {
+            std::thread::sleep(std::time::Duration::from_millis(750));
+            let current = ref_snapshot(&path);
+            if current != previous {
+                if let Ok(batch) = newly_reachable(&path, &previous, &current) {
+                    if !batch.is_empty() {
+                        let _ = app.emit("watch://update", batch);
+                    }
+                }
+                previous = current;
+            }
+        }
+    });
 
     Ok(())
 }
 
+/// Stop the watcher previously started for `path` by [`watch_repo`].
 #[tauri::command]
-pub fn get_commit_changes(path: String, oid: String) -> Result<Vec<FileChange>, String> {
-    let repo = Repository::open(&path).map_err(|e| e.to_string())?;
-    let commit = repo.find_commit(Oid::from_str(&oid).map_err(|e| e.to_string())?).map_err(|e| e.to_string())?;
-    let tree = commit.tree().map_err(|e| e.to_string())?;
+pub fn stop_watch(state: tauri::State<'_, WatcherState>, path: String) -> Result<(), String> {
+    use std::sync::atomic::Ordering;
 
-    let parent_tree = if commit.parent_count() > 0 {
-        Some(commit.parent(0).map_err(|e| e.to_string())?.tree().map_err(|e| e.to_string())?)
-    } else {
-        None
+    let mut watchers = state.watchers.lock().map_err(|e| e.to_string())?;
+    if let Some(stop) = watchers.remove(&path) {
+        stop.store(true, Ordering::Relaxed);
+    }
+    Ok(())
+}
+
+/// Snapshot every ref (plus HEAD) as a `name -> oid` map. A change between two
+/// snapshots is what the watcher reacts to.
+fn ref_snapshot(path: &str) -> std::collections::HashMap<String, String> {
+    let mut snapshot = std::collections::HashMap::new();
+    let repo = match Repository::open(path) {
+        Ok(r) => r,
+        Err(_) => return snapshot,
     };
+    if let Ok(references) = repo.references() {
+        for reference in references.flatten() {
+            if let (Some(name), Some(target)) = (reference.name(), reference.target()) {
+                snapshot.insert(name.to_string(), target.to_string());
+            }
+        }
+    }
+    if let Ok(head) = repo.head() {
+        if let Some(target) = head.target() {
+            snapshot.insert("HEAD".to_string(), target.to_string());
+        }
+    }
+    snapshot
+}
 
-    let diff = repo.diff_tree_to_tree(parent_tree.as_ref(), Some(&tree), None).map_err(|e| e.to_string())?;
+/// Split a fully-qualified ref name into a display shorthand and a kind,
+/// matching the vocabulary used by [`get_commits`].
+fn classify_ref(name: &str) -> (String, String) {
+    if name == "HEAD" {
+        ("HEAD".to_string(), "HEAD".to_string())
+    } else if let Some(short) = name.strip_prefix("refs/heads/") {
+        (short.to_string(), "branch".to_string())
+    } else if let Some(short) = name.strip_prefix("refs/remotes/") {
+        (short.to_string(), "remote".to_string())
+    } else if let Some(short) = name.strip_prefix("refs/tags/") {
+        (short.to_string(), "tag".to_string())
+    } else {
+        (name.to_string(), "other".to_string())
+    }
+}
 
-    let mut changes = Vec::new();
-    diff.foreach(&mut |delta, _| {
-        let path = delta.new_file().path().unwrap_or(delta.old_file().path().unwrap());
-        let status = format!("{:?}", delta.status());
-        changes.push(FileChange {
-            path: path.to_string_lossy().to_string(),
-            status,
-        });
-        true
-    }, None, None, None).map_err(|e| e.to_string())?;
+/// Walk the commits made reachable by a ref move: push the current tips and
+/// hide the previous ones so only the incremental batch is produced.
+fn newly_reachable(
+    path: &str,
+    previous: &std::collections::HashMap<String, String>,
+    current: &std::collections::HashMap<String, String>,
+) -> Result<Vec<CommitInfo>, String> {
+    let repo = Repository::open(path).map_err(|e| e.to_string())?;
+
+    let mut ref_map: std::collections::HashMap<String, Vec<RefInfo>> =
+        std::collections::HashMap::new();
+    for (name, oid) in current {
+        let (short, kind) = classify_ref(name);
+        ref_map
+            .entry(oid.clone())
+            .or_default()
+            .push(RefInfo { name: short, kind });
+    }
+
+    let mut revwalk = repo.revwalk().map_err(|e| e.to_string())?;
+    revwalk
+        .set_sorting(Sort::TOPOLOGICAL | Sort::TIME)
+        .map_err(|e| e.to_string())?;
+
+    for oid in current.values() {
+        if let Ok(oid) = Oid::from_str(oid) {
+            let _ = revwalk.push(oid);
+        }
+    }
+    for oid in previous.values() {
+        if let Ok(oid) = Oid::from_str(oid) {
+            let _ = revwalk.hide(oid);
+        }
+    }
+
+    let mut batch = Vec::new();
+    for oid in revwalk {
+        let oid = oid.map_err(|e| e.to_string())?;
+        let commit = repo.find_commit(oid).map_err(|e| e.to_string())?;
+        let refs = ref_map.get(&oid.to_string()).cloned().unwrap_or_default();
+        batch.push(build_commit_info(&repo, &commit, refs));
+    }
 
-    Ok(changes)
+    Ok(batch)
 }
 
-#[tauri::command]
-pub fn compare_commits(path: String, oid1: String, oid2: String) -> Result<Vec<FileChange>, String> {
-    let repo = Repository::open(&path).map_err(|e| e.to_string())?;
-    let commit1 = repo.find_commit(Oid::from_str(&oid1).map_err(|e| e.to_string())?).map_err(|e| e.to_string())?;
-    let commit2 = repo.find_commit(Oid::from_str(&oid2).map_err(|e| e.to_string())?).map_err(|e| e.to_string())?;
+fn verify_ssh_signature(
+    oid: &str,
+    keyring_path: &str,
+    sig: &[u8],
+    payload: &[u8],
+) -> Result<CommitSignature, String> {
+    use std::io::Write;
+    use std::process::Command;
 
-    let tree1 = commit1.tree().map_err(|e| e.to_string())?;
-    let tree2 = commit2.tree().map_err(|e| e.to_string())?;
+    // `ssh-keygen -Y verify` drives SSH signature checking against an
+    // `allowed_signers` file, the same mechanism git uses for `gpg.format=ssh`.
+    // The temp paths include the commit oid so concurrent verifications in the
+    // same process don't clobber each other's files.
+    let tmp = std::env::temp_dir();
+    let stamp = format!("{}-{oid}", std::process::id());
+    let sig_path = tmp.join(format!("git-tree-{stamp}.sig"));
+    let payload_path = tmp.join(format!("git-tree-{stamp}.payload"));
 
-    let diff = repo.diff_tree_to_tree(Some(&tree1), Some(&tree2), None).map_err(|e| e.to_string())?;
+    std::fs::write(&sig_path, sig).map_err(|e| e.to_string())?;
+    std::fs::write(&payload_path, payload).map_err(|e| e.to_string())?;
 
-    let mut changes = Vec::new();
-    diff.foreach(&mut |delta, _| {
-        let path = delta.new_file().path().unwrap_or(delta.old_file().path().unwrap());
-        let status = format!("{:?}", delta.status());
-        changes.push(FileChange {
-            path: path.to_string_lossy().to_string(),
-            status,
-        });
-        true
-    }, None, None, None).map_err(|e| e.to_string())?;
+    let cleanup = || {
+        let _ = std::fs::remove_file(&sig_path);
+        let _ = std::fs::remove_file(&payload_path);
+    };
+
+    // Identify the trusted principal that owns the signing key, if any.
+    let principals = Command::new("ssh-keygen")
+        .args(["-Y", "find-principals", "-s"])
+        .arg(&sig_path)
+        .args(["-f"])
+        .arg(keyring_path)
+        .output()
+        .map_err(|e| e.to_string())?;
+
+    let signer = String::from_utf8_lossy(&principals.stdout)
+        .lines()
+        .next()
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty());
 
-    Ok(changes)
+    let signer = match signer {
+        Some(s) => s,
+        None => {
+            cleanup();
+            // Signature is present but no trusted signer matched it.
+            return Ok(CommitSignature::SignedUntrusted {
+                signer: "unknown".to_string(),
+            });
+        }
+    };
+
+    let payload_bytes = std::fs::read(&payload_path).map_err(|e| e.to_string())?;
+    let mut verify = Command::new("ssh-keygen")
+        .args(["-Y", "verify", "-n", "git", "-I", &signer, "-f"])
+        .arg(keyring_path)
+        .args(["-s"])
+        .arg(&sig_path)
+        .stdin(std::process::Stdio::piped())
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::null())
+        .spawn()
+        .map_err(|e| e.to_string())?;
+    if let Some(stdin) = verify.stdin.as_mut() {
+        stdin.write_all(&payload_bytes).map_err(|e| e.to_string())?;
+    }
+    let status = verify.wait().map_err(|e| e.to_string())?;
+    cleanup();
+
+    if status.success() {
+        Ok(CommitSignature::SignedValid { signer })
+    } else {
+        Ok(CommitSignature::SignedBad)
+    }
 }
 
-#[tauri::command]
-pub fn clone_repo(url: String, path: String) -> Result<String, String> {
-    let _ = Repository::clone(&url, &path).map_err(|e| e.to_string())?;
-    Ok("Cloned successfully".to_string())
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Build a bare [`CommitInfo`] for the graph algorithms, which only read
+    /// `oid`, `parents` and `refs`.
+    fn commit(oid: &str, parents: &[&str], refs: &[&str]) -> CommitInfo {
+        CommitInfo {
+            oid: oid.to_string(),
+            parents: parents.iter().map(|p| p.to_string()).collect(),
+            author: String::new(),
+            email: String::new(),
+            date: 0,
+            message: String::new(),
+            refs: refs
+                .iter()
+                .map(|r| RefInfo {
+                    name: r.to_string(),
+                    kind: "branch".to_string(),
+                })
+                .collect(),
+            is_merge: parents.len() > 1,
+            trivial_merge: false,
+            signature: CommitSignature::Unverified,
+            lane: 0,
+            edges: Vec::new(),
+        }
+    }
+
+    fn find<'a>(commits: &'a [CommitInfo], oid: &str) -> &'a CommitInfo {
+        commits.iter().find(|c| c.oid == oid).expect("commit present")
+    }
+
+    // Topo-ordered (children before parents) DAG with a merge at the tip and a
+    // branch point at the root:
+    //
+    //   M      merge of A and B
+    //   |\
+    //   A B    two branches off C
+    //   |/
+    //   C      root / branch point
+    #[test]
+    fn assign_lanes_merge_and_branch() {
+        let mut commits = vec![
+            commit("M", &["A", "B"], &[]),
+            commit("A", &["C"], &[]),
+            commit("B", &["C"], &[]),
+            commit("C", &[], &[]),
+        ];
+        assign_lanes(&mut commits);
+
+        // The merge sits on lane 0; its second parent branches to lane 1.
+        assert_eq!(find(&commits, "M").lane, 0);
+        // First parent keeps the merge's lane, second parent gets a new one.
+        assert_eq!(find(&commits, "A").lane, 0);
+        assert_eq!(find(&commits, "B").lane, 1);
+        // At the branch point both lanes collapse back into lane 0.
+        let c = find(&commits, "C");
+        assert_eq!(c.lane, 0);
+        assert!(c.edges.iter().any(|e| e.from == 1 && e.to == 0));
+    }
+
+    // A commit's first parent must stay first after simplification so the lane
+    // pass labels the mainline correctly (regression: parents were sorted).
+    #[test]
+    fn simplify_preserves_first_parent_order() {
+        // "b" is the mainline (first) parent, "a" the merged branch; both carry
+        // refs so they're kept, and "a" < "b" lexically to expose a stray sort.
+        let all = vec![
+            commit("m", &["b", "a"], &[]),
+            commit("b", &["r"], &["main"]),
+            commit("a", &["r"], &["feature"]),
+            commit("r", &[], &["root"]),
+        ];
+        let simplified = simplify_commits(&all);
+
+        let m = find(&simplified, "m");
+        assert_eq!(m.parents, vec!["b".to_string(), "a".to_string()]);
+    }
+
+    // Plain commits between interesting ones are collapsed, with parents
+    // re-linked to the nearest interesting ancestor.
+    #[test]
+    fn simplify_relinks_to_nearest_interesting_ancestor() {
+        let all = vec![
+            commit("tip", &["mid"], &["main"]),
+            commit("mid", &["root"], &[]),
+            commit("root", &[], &["base"]),
+        ];
+        let simplified = simplify_commits(&all);
+
+        assert!(simplified.iter().all(|c| c.oid != "mid"));
+        assert_eq!(find(&simplified, "tip").parents, vec!["root".to_string()]);
+    }
 }
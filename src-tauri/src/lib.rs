@@ -10,13 +10,23 @@ fn greet(name: &str) -> String {
 pub fn run() {
     tauri::Builder::default()
         .plugin(tauri_plugin_opener::init())
+        .manage(git::WatcherState::default())
+        .manage(git::PaginationState::default())
         .invoke_handler(tauri::generate_handler![
             greet,
             git::get_commits,
             git::checkout_ref,
             git::get_commit_changes,
             git::compare_commits,
-            git::clone_repo
+            git::clone_repo,
+            git::verify_commit,
+            git::fetch_remote,
+            git::pull_ref,
+            git::push_ref,
+            git::watch_repo,
+            git::stop_watch,
+            git::get_file_diff,
+            git::get_commits_page
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");